@@ -4,9 +4,13 @@ use crate::{
     error::GleamExpect,
     format, pretty,
     project::{Analysed, ModuleOrigin, OutputFile, ProjectConfig},
+    typ,
 };
 use askama::Template;
 use itertools::Itertools;
+use pulldown_cmark::CowStr;
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 const MAX_COLUMNS: isize = 65;
@@ -18,10 +22,14 @@ pub fn generate_html(
     files: &mut Vec<OutputFile>,
     dir: &PathBuf,
 ) {
-    let modules = analysed.iter().filter(|m| m.origin == ModuleOrigin::Src);
+    let modules: Vec<_> = analysed
+        .iter()
+        .filter(|m| m.origin == ModuleOrigin::Src)
+        .collect();
+    let symbols = build_symbol_index(&modules);
 
     let modules_links: Vec<_> = modules
-        .clone()
+        .iter()
         .map(|m| {
             let name = m.name.join("/");
             let path = name.clone();
@@ -33,49 +41,57 @@ pub fn generate_html(
         path: "".to_string(),
     }];
     let links = &[];
+    let mut search_index: Vec<SearchIndexEntry> = Vec::new();
+    let mut json_modules: Vec<JsonModule> = Vec::new();
 
     // Generate README page
+    let readme_resolver = LinkResolver {
+        current_module: "",
+        unnest: ".",
+        imports: vec![],
+        symbols: &symbols,
+    };
+    let readme_content = std::fs::read_to_string(project_config.root.join("README.md"))
+        .map(|markdown| render_markdown(&markdown, &readme_resolver))
+        .unwrap_or_default();
     let readme = PageTemplate {
         unnest: ".".to_string(),
         links,
         pages,
         modules: &modules_links,
-        content: "", // TODO
+        content: &readme_content,
         project_name: &project_config.name,
         page_title: &project_config.name,
-        project_version: "", // TODO
+        project_version: &project_config.version,
     };
     files.push(OutputFile {
         path: dir.join("index.html"),
         text: readme.render().gleam_expect("README template rendering"),
     });
 
-    // Generate module documentation pages
-    for module in modules {
-        let template = ModuleTemplate {
-            unnest: module.name.iter().map(|_| "..").intersperse("/").collect(),
-            links,
-            pages,
-            module_name: module.name.join("/"),
-            documentation: "",
-            modules: modules_links.as_slice(),
-            project_name: &project_config.name,
-            page_title: &project_config.name,
-            project_version: "", // TODO
-            functions: module.ast.statements.iter().flat_map(function).collect(),
-            types: module.ast.statements.iter().flat_map(type_).collect(),
-        };
-        let mut path = dir.clone();
-        for segment in module.name.iter() {
-            path.push(segment);
-        }
-        path.push("index.html");
-        files.push(OutputFile {
-            path,
-            text: template
-                .render()
-                .gleam_expect("Module documentation template rendering"),
-        });
+    // Generate module documentation pages. Each module is rendered independently
+    // (including the markdown conversion of every doc comment, historically the
+    // slowest part of doc generation), so this fans out across a rayon pool.
+    let module_outputs: Vec<ModuleOutput> = modules
+        .par_iter()
+        .map(|module| {
+            render_module(
+                module,
+                &modules_links,
+                pages,
+                links,
+                &symbols,
+                project_config,
+                dir,
+            )
+        })
+        .collect();
+
+    for output in module_outputs {
+        files.push(output.index_file);
+        files.push(output.source_file);
+        search_index.extend(output.search_entries);
+        json_modules.push(output.json_module);
     }
 
     // Render static assets
@@ -83,78 +99,488 @@ pub fn generate_html(
         path: dir.join("index.css"),
         text: std::include_str!("../templates/index.css").to_string(),
     });
+    files.push(OutputFile {
+        path: dir.join("search.js"),
+        text: std::include_str!("../templates/search.js").to_string(),
+    });
+
+    // Render the search index consumed by search.js
+    let search_index_json =
+        serde_json::to_string(&search_index).gleam_expect("search index serialization");
+    files.push(OutputFile {
+        path: dir.join("search-index.json"),
+        text: search_index_json,
+    });
+
+    // Render the machine-readable documentation model for external tooling
+    let docs_json = serde_json::to_string(&json_modules).gleam_expect("docs.json serialization");
+    files.push(OutputFile {
+        path: dir.join("docs.json"),
+        text: docs_json,
+    });
+}
+
+struct ModuleOutput {
+    index_file: OutputFile,
+    source_file: OutputFile,
+    search_entries: Vec<SearchIndexEntry>,
+    json_module: JsonModule,
 }
 
-fn function<'a>(statement: &'a TypedStatement) -> Option<Function<'a>> {
+fn render_module(
+    module: &Analysed,
+    modules_links: &[Link],
+    pages: &[Link],
+    links: &[Link],
+    symbols: &HashMap<String, Vec<String>>,
+    project_config: &ProjectConfig,
+    dir: &PathBuf,
+) -> ModuleOutput {
+    let module_path = module.name.join("/");
+    let unnest: String = module.name.iter().map(|_| "..").intersperse("/").collect();
+    let resolver = LinkResolver {
+        current_module: &module_path,
+        unnest: &unnest,
+        imports: import_aliases(&module.ast.statements),
+        symbols,
+    };
+    let functions: Vec<Function> = module
+        .ast
+        .statements
+        .iter()
+        .flat_map(|s| function(s, &resolver, Some(&module.code)))
+        .collect();
+    let types: Vec<Type> = module
+        .ast
+        .statements
+        .iter()
+        .flat_map(|s| type_(s, &resolver, Some(&module.code)))
+        .collect();
+
+    let mut search_entries: Vec<SearchIndexEntry> = functions
+        .iter()
+        .map(|f| {
+            SearchIndexEntry::new(
+                f.name,
+                &module_path,
+                "function",
+                &f.signature,
+                &f.documentation,
+            )
+        })
+        .collect();
+    search_entries.extend(types.iter().map(|t| {
+        SearchIndexEntry::new(
+            t.name,
+            &module_path,
+            "type",
+            &t.definition,
+            &t.documentation,
+        )
+    }));
+
+    let json_module = JsonModule {
+        name: module_path.clone(),
+        documentation: module.ast.documentation.clone(),
+        functions: functions.iter().map(JsonItem::from_function).collect(),
+        types: types.iter().map(JsonItem::from_type).collect(),
+    };
+
+    let template = ModuleTemplate {
+        unnest: unnest.clone(),
+        links,
+        pages,
+        module_name: module_path,
+        documentation: "",
+        modules: modules_links,
+        project_name: &project_config.name,
+        page_title: &project_config.name,
+        project_version: &project_config.version,
+        functions,
+        types,
+    };
+    let mut path = dir.clone();
+    for segment in module.name.iter() {
+        path.push(segment);
+    }
+    path.push("index.html");
+    let index_file = OutputFile {
+        path,
+        text: template
+            .render()
+            .gleam_expect("Module documentation template rendering"),
+    };
+
+    let source_template = SourceTemplate {
+        unnest,
+        links,
+        pages,
+        module_name: module.name.join("/"),
+        modules: modules_links,
+        project_name: &project_config.name,
+        page_title: &project_config.name,
+        project_version: &project_config.version,
+        source: highlight_source(&module.code),
+    };
+    let mut source_path = dir.clone();
+    for segment in module.name.iter() {
+        source_path.push(segment);
+    }
+    source_path.push("source.html");
+    let source_file = OutputFile {
+        path: source_path,
+        text: source_template
+            .render()
+            .gleam_expect("Source template rendering"),
+    };
+
+    ModuleOutput {
+        index_file,
+        source_file,
+        search_entries,
+        json_module,
+    }
+}
+
+fn function<'a>(
+    statement: &'a TypedStatement,
+    resolver: &LinkResolver,
+    source: Option<&str>,
+) -> Option<Function<'a>> {
     match statement {
         Statement::ExternalFn {
             public: true,
             name,
             doc,
+            args,
+            retrn,
+            location,
             ..
         } => Some(Function {
             name,
-            signature: "".to_string(),
+            signature: external_fn_signature(name, args, retrn),
             documentation: match doc {
                 None => "".to_string(),
-                Some(d) => render_markdown(d),
+                Some(d) => render_markdown(d, resolver),
             },
+            doc_raw: doc.as_deref(),
+            source: source.map(|code| source_link(code, location.start)),
         }),
 
         Statement::Fn {
             public: true,
             name,
             doc,
+            args,
+            return_type,
+            location,
             ..
         } => Some(Function {
             name,
-            signature: "".to_string(),
+            signature: fn_signature(name, args, return_type),
             documentation: match doc {
                 None => "".to_string(),
-                Some(d) => render_markdown(d),
+                Some(d) => render_markdown(d, resolver),
             },
+            doc_raw: doc.as_deref(),
+            source: source.map(|code| source_link(code, location.start)),
         }),
 
         _ => None,
     }
 }
 
-fn render_markdown(text: &str) -> String {
+fn fn_signature(
+    name: &str,
+    args: &[ast::TypedArg],
+    return_type: &std::sync::Arc<typ::Type>,
+) -> String {
+    use crate::pretty::*;
+    let printer = typ::pretty::Printer::new();
+    let doc = "pub fn "
+        .to_doc()
+        .append(name.to_string())
+        .append(format::wrap_args(
+            args.iter().map(|arg| typed_arg_doc(&printer, arg)),
+        ))
+        .append(" -> ")
+        .append(printer.pretty_print(return_type, 0));
+    pretty::format(MAX_COLUMNS, doc)
+}
+
+fn external_fn_signature(name: &str, args: &[ast::ExternalFnArg], retrn: &ast::TypeAst) -> String {
+    use crate::pretty::*;
+    let doc = "pub external fn "
+        .to_doc()
+        .append(name.to_string())
+        .append(format::wrap_args(args.iter().map(external_fn_arg_doc)))
+        .append(" -> ")
+        .append(format::type_ast(retrn));
+    pretty::format(MAX_COLUMNS, doc)
+}
+
+fn typed_arg_doc<'a>(
+    printer: &typ::pretty::Printer,
+    arg: &'a ast::TypedArg,
+) -> pretty::Document<'a> {
+    use crate::pretty::*;
+    let typ = printer.pretty_print(&arg.typ, 0);
+    match arg.names.get_label() {
+        Some(label) => label.to_string().to_doc().append(": ").append(typ),
+        None => typ.to_doc(),
+    }
+}
+
+fn external_fn_arg_doc(arg: &ast::ExternalFnArg) -> pretty::Document {
+    use crate::pretty::*;
+    let typ = format::type_ast(&arg.annotation);
+    match &arg.label {
+        Some(label) => label.clone().to_doc().append(": ").append(typ),
+        None => typ.to_doc(),
+    }
+}
+
+fn render_markdown(text: &str, resolver: &LinkResolver) -> String {
+    use pulldown_cmark::{Event, Options, Parser, Tag};
+
+    // Shortcut references like `[Result]` or `[list.map]` have no definition
+    // elsewhere in the doc comment, so pulldown-cmark treats them as "broken"
+    // links and only emits a `Tag::Link` for them via this callback.
+    let mut resolve_broken_link = |broken_link: &str, _title: &str| {
+        resolver
+            .resolve(broken_link)
+            .map(|url| (url, String::new()))
+    };
+
     let mut s = String::with_capacity(text.len() * 3 / 2);
-    let p = pulldown_cmark::Parser::new(&*text);
+    let p = Parser::new_with_broken_link_callback(
+        text,
+        Options::empty(),
+        Some(&mut resolve_broken_link),
+    )
+    .map(|event| match event {
+        Event::Start(Tag::Link(link_type, dest, title)) => {
+            let dest = resolver.resolve(&dest).map(CowStr::from).unwrap_or(dest);
+            Event::Start(Tag::Link(link_type, dest, title))
+        }
+        Event::End(Tag::Link(link_type, dest, title)) => {
+            let dest = resolver.resolve(&dest).map(CowStr::from).unwrap_or(dest);
+            Event::End(Tag::Link(link_type, dest, title))
+        }
+        event => event,
+    });
     pulldown_cmark::html::push_html(&mut s, p);
     s
 }
 
-fn type_<'a>(statement: &'a TypedStatement) -> Option<Type<'a>> {
+/// Resolves intra-doc link destinations (e.g. `[Result]`, `[list.map]`) against
+/// the public functions and types visible from the module being rendered.
+struct LinkResolver<'a> {
+    current_module: &'a str,
+    unnest: &'a str,
+    imports: Vec<(String, String)>,
+    symbols: &'a HashMap<String, Vec<String>>,
+}
+
+impl<'a> LinkResolver<'a> {
+    fn resolve(&self, target: &str) -> Option<String> {
+        if let Some((qualifier, name)) = target.split_once('.') {
+            let module_path = self
+                .imports
+                .iter()
+                .find(|(alias, _)| alias == qualifier)
+                .map(|(_, path)| path.clone())
+                .unwrap_or_else(|| qualifier.to_string());
+            return self
+                .symbols
+                .get(&module_path)
+                .filter(|names| names.iter().any(|n| n == name))
+                .map(|_| format!("{}/{}/index.html#{}", self.unnest, module_path, name));
+        }
+
+        if let Some(names) = self.symbols.get(self.current_module) {
+            if names.iter().any(|n| n == target) {
+                return Some(format!("#{}", target));
+            }
+        }
+
+        self.imports.iter().find_map(|(_, module_path)| {
+            self.symbols
+                .get(module_path)
+                .filter(|names| names.iter().any(|n| n == target))
+                .map(|_| format!("{}/{}/index.html#{}", self.unnest, module_path, target))
+        })
+    }
+}
+
+fn import_aliases(statements: &[TypedStatement]) -> Vec<(String, String)> {
+    statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Import {
+                module, as_name, ..
+            } => {
+                let path = module.join("/");
+                let alias = as_name
+                    .clone()
+                    .or_else(|| module.last().cloned())
+                    .unwrap_or_else(|| path.clone());
+                Some((alias, path))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn build_symbol_index(modules: &[&Analysed]) -> HashMap<String, Vec<String>> {
+    modules
+        .iter()
+        .map(|module| {
+            let path = module.name.join("/");
+            let names = module
+                .ast
+                .statements
+                .iter()
+                .filter_map(|statement| match statement {
+                    Statement::Fn {
+                        public: true, name, ..
+                    }
+                    | Statement::ExternalFn {
+                        public: true, name, ..
+                    }
+                    | Statement::CustomType {
+                        public: true, name, ..
+                    }
+                    | Statement::ExternalType {
+                        public: true, name, ..
+                    } => Some(name.clone()),
+                    Statement::TypeAlias {
+                        public: true,
+                        alias,
+                        ..
+                    } => Some(alias.clone()),
+                    _ => None,
+                })
+                .collect();
+            (path, names)
+        })
+        .collect()
+}
+
+/// A link from an item's docs to its definition on the module's `source.html` page.
+fn source_link(code: &str, byte_offset: usize) -> String {
+    format!("source.html#L{}", line_number(code, byte_offset))
+}
+
+fn line_number(code: &str, byte_offset: usize) -> usize {
+    code[..byte_offset.min(code.len())].matches('\n').count() + 1
+}
+
+const GLEAM_KEYWORDS: &[&str] = &[
+    "as", "assert", "case", "const", "external", "fn", "if", "import", "let", "opaque", "pub",
+    "todo", "try", "tuple", "type", "use",
+];
+
+/// Renders a module's source with basic Gleam token highlighting and a `#L<n>`
+/// anchor on every line, for the `[src]` links next to each item.
+fn highlight_source(code: &str) -> String {
+    code.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line_number = i + 1;
+            format!(
+                "<span id=\"L{n}\" class=\"line\"><span class=\"line-number\">{n}</span>{code}</span>\n",
+                n = line_number,
+                code = highlight_line(line)
+            )
+        })
+        .collect()
+}
+
+fn highlight_line(line: &str) -> String {
+    match line.find("//") {
+        Some(comment_start) => {
+            let (code, comment) = line.split_at(comment_start);
+            format!(
+                "{}<span class=\"comment\">{}</span>",
+                highlight_tokens(code),
+                html_escape(comment)
+            )
+        }
+        None => highlight_tokens(line),
+    }
+}
+
+fn highlight_tokens(code: &str) -> String {
+    code.split_inclusive(char::is_whitespace)
+        .map(|word| {
+            let trimmed = word.trim();
+            if trimmed.len() > 1 && trimmed.starts_with('"') && trimmed.ends_with('"') {
+                word.replacen(
+                    trimmed,
+                    &format!("<span class=\"string\">{}</span>", html_escape(trimmed)),
+                    1,
+                )
+            } else if GLEAM_KEYWORDS.contains(&trimmed) {
+                word.replacen(
+                    trimmed,
+                    &format!("<span class=\"keyword\">{}</span>", trimmed),
+                    1,
+                )
+            } else {
+                html_escape(word)
+            }
+        })
+        .collect()
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn type_<'a>(
+    statement: &'a TypedStatement,
+    resolver: &LinkResolver,
+    source: Option<&str>,
+) -> Option<Type<'a>> {
     match statement {
         Statement::ExternalType {
             public: true,
             name,
             doc,
             args,
+            location,
             ..
         } => Some(Type {
             name,
             definition: external_type(name.as_str(), args),
             documentation: match doc {
                 None => "".to_string(),
-                Some(d) => render_markdown(d),
+                Some(d) => render_markdown(d, resolver),
             },
+            doc_raw: doc.as_deref(),
+            source: source.map(|code| source_link(code, location.start)),
         }),
 
         Statement::CustomType {
             public: true,
             name,
+            args,
+            constructors,
             doc,
+            location,
             ..
         } => Some(Type {
             name,
-            definition: "".to_string(),
+            definition: custom_type(name, args, constructors),
             documentation: match doc {
                 None => "".to_string(),
-                Some(d) => render_markdown(d),
+                Some(d) => render_markdown(d, resolver),
             },
+            doc_raw: doc.as_deref(),
+            source: source.map(|code| source_link(code, location.start)),
         }),
 
         Statement::TypeAlias {
@@ -163,14 +589,17 @@ fn type_<'a>(statement: &'a TypedStatement) -> Option<Type<'a>> {
             resolved_type: typ,
             doc,
             args,
+            location,
             ..
         } => Some(Type {
             name,
             definition: type_alias(name, args, typ),
             documentation: match doc {
                 None => "".to_string(),
-                Some(d) => render_markdown(d),
+                Some(d) => render_markdown(d, resolver),
             },
+            doc_raw: doc.as_deref(),
+            source: source.map(|code| source_link(code, location.start)),
         }),
 
         _ => None,
@@ -190,6 +619,50 @@ fn external_type(name: &str, args: &[String]) -> String {
     pretty::format(MAX_COLUMNS, doc)
 }
 
+fn custom_type(name: &str, args: &[String], constructors: &[ast::RecordConstructor]) -> String {
+    use crate::pretty::*;
+    let doc = "pub type "
+        .to_doc()
+        .append(name.to_string())
+        .append(if args.is_empty() {
+            nil()
+        } else {
+            format::wrap_args(args.iter().map(|e| e.clone().to_doc()))
+        })
+        .append(" {")
+        .append(
+            concat(constructors.iter().map(|c| line().append(constructor(c))))
+                .nest(INDENT)
+                .group(),
+        )
+        .append(line())
+        .append("}");
+    pretty::format(MAX_COLUMNS, doc)
+}
+
+fn constructor(constructor: &ast::RecordConstructor) -> pretty::Document {
+    use crate::pretty::*;
+    constructor
+        .name
+        .to_string()
+        .to_doc()
+        .append(if constructor.args.is_empty() {
+            nil()
+        } else {
+            format::wrap_args(constructor.args.iter().map(constructor_arg))
+        })
+}
+
+fn constructor_arg(arg: &(Option<String>, ast::TypeAst)) -> pretty::Document {
+    use crate::pretty::*;
+    let (label, typ) = arg;
+    let typ = format::type_ast(typ);
+    match label {
+        Some(label) => label.clone().to_doc().append(": ").append(typ),
+        None => typ.to_doc(),
+    }
+}
+
 fn type_alias(name: &str, args: &[String], typ: &ast::TypeAst) -> String {
     use crate::pretty::*;
     let doc = "pub type "
@@ -210,16 +683,88 @@ struct Link {
     path: String,
 }
 
+#[derive(serde::Serialize)]
+struct SearchIndexEntry {
+    name: String,
+    module_path: String,
+    kind: &'static str,
+    signature: String,
+    doc_summary: String,
+}
+
+impl SearchIndexEntry {
+    fn new(
+        name: &str,
+        module_path: &str,
+        kind: &'static str,
+        signature: &str,
+        documentation: &str,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            module_path: module_path.to_string(),
+            kind,
+            signature: signature.to_string(),
+            doc_summary: documentation.lines().next().unwrap_or("").to_string(),
+        }
+    }
+}
+
+/// Serializable mirror of a module's public API, written to `docs.json` for
+/// external tooling (editors, package registries, doc search sites) to consume.
+#[derive(serde::Serialize)]
+struct JsonModule {
+    name: String,
+    documentation: Option<String>,
+    functions: Vec<JsonItem>,
+    types: Vec<JsonItem>,
+}
+
+#[derive(serde::Serialize)]
+struct JsonItem {
+    name: String,
+    kind: &'static str,
+    signature: String,
+    doc_raw: String,
+    doc_html: String,
+}
+
+impl JsonItem {
+    fn from_function(function: &Function) -> Self {
+        Self {
+            name: function.name.to_string(),
+            kind: "function",
+            signature: function.signature.clone(),
+            doc_raw: function.doc_raw.unwrap_or("").to_string(),
+            doc_html: function.documentation.clone(),
+        }
+    }
+
+    fn from_type(typ: &Type) -> Self {
+        Self {
+            name: typ.name.to_string(),
+            kind: "type",
+            signature: typ.definition.clone(),
+            doc_raw: typ.doc_raw.unwrap_or("").to_string(),
+            doc_html: typ.documentation.clone(),
+        }
+    }
+}
+
 struct Function<'a> {
     name: &'a str,
     signature: String,
     documentation: String,
+    doc_raw: Option<&'a str>,
+    source: Option<String>,
 }
 
 struct Type<'a> {
     name: &'a str,
     definition: String,
     documentation: String,
+    doc_raw: Option<&'a str>,
+    source: Option<String>,
 }
 
 #[derive(Template)]
@@ -250,3 +795,17 @@ struct ModuleTemplate<'a> {
     types: Vec<Type<'a>>,
     documentation: &'a str,
 }
+
+#[derive(Template)]
+#[template(path = "documentation_source.html")]
+struct SourceTemplate<'a> {
+    unnest: String,
+    page_title: &'a str,
+    module_name: String,
+    project_name: &'a str,
+    project_version: &'a str,
+    pages: &'a [Link],
+    links: &'a [Link],
+    modules: &'a [Link],
+    source: String,
+}
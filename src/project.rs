@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleOrigin {
+    Src,
+    Test,
+    Dependency,
+}
+
+pub struct OutputFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+pub struct Analysed {
+    pub name: Vec<String>,
+    pub origin: ModuleOrigin,
+    pub code: String,
+    pub ast: crate::ast::TypedModule,
+}
+
+pub struct ProjectConfig {
+    pub name: String,
+    pub version: String,
+    pub root: PathBuf,
+    pub deps: Vec<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct ProjectConfigToml {
+    name: String,
+    version: Option<String>,
+    #[serde(default)]
+    deps: Vec<String>,
+}
+
+/// Reads `gleam.toml` from the project root, used to populate the sidebar and
+/// page titles in the generated documentation.
+pub fn read_project_config(root: &PathBuf) -> ProjectConfig {
+    let toml = std::fs::read_to_string(root.join("gleam.toml"))
+        .unwrap_or_else(|_| "name = \"\"".to_string());
+    let parsed: ProjectConfigToml =
+        toml::from_str(&toml).unwrap_or_else(|_| ProjectConfigToml {
+            name: "".to_string(),
+            version: None,
+            deps: vec![],
+        });
+    ProjectConfig {
+        name: parsed.name,
+        version: parsed.version.unwrap_or_else(|| "0.1.0".to_string()),
+        root: root.clone(),
+        deps: parsed.deps,
+    }
+}